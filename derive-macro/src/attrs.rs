@@ -0,0 +1,157 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Attribute, Expr, ExprCall, Field, LitInt};
+
+/// Whether `field` carries one of the generation-overriding attributes
+/// (`#[range(...)]`, `#[len(...)]`, `#[distribution(...)]`) rather than
+/// using the blanket
+/// [`GenerateRandom`](../../generate_random/trait.GenerateRandom.html) impl.
+pub fn has_field_attr(field: &Field) -> bool {
+    field.attrs.iter().any(|attr| {
+        attr.path.is_ident("range") || attr.path.is_ident("len") || attr.path.is_ident("distribution")
+    })
+}
+
+/// Build the expression used to generate a single field's value, honoring
+/// `#[range(...)]`, `#[len(...)]` and `#[distribution(...)]` attributes
+/// when present.
+///
+/// The fallback case forwards the in-scope `depth` variable unchanged
+/// rather than incrementing it: `depth` is only ever advanced by the
+/// field's own container type (`Box`, `Vec`, `Option`, ...) when it
+/// recurses into its inner value, so that one level of nesting advances
+/// the counter by exactly one.
+pub fn field_generator(field: &Field) -> TokenStream {
+    if let Some(range) = parse_range_attr(field, "range") {
+        return quote! { rng.gen_range(#range) };
+    }
+
+    if let Some(range) = parse_range_attr(field, "len") {
+        let ty = &field.ty;
+        return quote! {
+            <#ty as generate_random::GenerateRandomLen>::generate_random_with_len(rng, #range)
+        };
+    }
+
+    if let Some(call) = parse_distribution_attr(field) {
+        return call;
+    }
+
+    quote! { generate_random::GenerateRandom::generate_random_with_depth(rng, depth) }
+}
+
+/// Parse a `#[max_depth(n)]` attribute on the derived type into a literal
+/// overriding [`generate_random::DEFAULT_MAX_DEPTH`] for that type's
+/// recursion-depth guard, falling back to the crate default when absent.
+pub fn max_depth_expr(attrs: &[Attribute]) -> TokenStream {
+    let attr = attrs.iter().find(|attr| attr.path.is_ident("max_depth"));
+    match attr {
+        Some(attr) => {
+            let lit = attr
+                .parse_args::<LitInt>()
+                .unwrap_or_else(|_| panic!("expected an integer literal for `#[max_depth(...)]`"));
+            quote! { #lit }
+        }
+        None => quote! { generate_random::DEFAULT_MAX_DEPTH },
+    }
+}
+
+fn parse_range_attr(field: &Field, name: &str) -> Option<Expr> {
+    let attr = field.attrs.iter().find(|attr| attr.path.is_ident(name))?;
+    Some(
+        attr.parse_args::<Expr>()
+            .unwrap_or_else(|_| panic!("expected a range expression for `#[{name}(...)]`")),
+    )
+}
+
+/// Parse a `#[distribution(Normal(0.0, 1.0))]`-style attribute into a call
+/// to the corresponding `rand_distr` distribution's `sample` method, routed
+/// through `generate_random::distribution::sample`, which only exists when
+/// the crate's `rand_distr` feature is enabled.
+fn parse_distribution_attr(field: &Field) -> Option<TokenStream> {
+    let attr = field
+        .attrs
+        .iter()
+        .find(|attr| attr.path.is_ident("distribution"))?;
+
+    let call = attr.parse_args::<ExprCall>().unwrap_or_else(|_| {
+        panic!("expected a call expression for `#[distribution(...)]`, e.g. `Normal(0.0, 1.0)`")
+    });
+
+    let distribution = match *call.func {
+        Expr::Path(ref path) => path
+            .path
+            .segments
+            .last()
+            .expect("expected a distribution name for `#[distribution(...)]`")
+            .ident
+            .clone(),
+        _ => panic!("expected a distribution name for `#[distribution(...)]`, e.g. `Normal(0.0, 1.0)`"),
+    };
+    let args = call.args;
+
+    Some(quote! {
+        generate_random::distribution::sample(
+            rand_distr::#distribution::new(#args)
+                .expect("invalid parameters for `#[distribution(...)]`"),
+            rng,
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_field_generator_range_attr() {
+        let field: Field = parse_quote! { #[range(0..100)] x: i32 };
+        let tokens = field_generator(&field).to_string();
+        assert!(tokens.contains("gen_range"));
+        assert!(tokens.contains("100"));
+    }
+
+    #[test]
+    fn test_field_generator_len_attr() {
+        let field: Field = parse_quote! { #[len(2..16)] x: Vec<u8> };
+        let tokens = field_generator(&field).to_string();
+        assert!(tokens.contains("GenerateRandomLen"));
+        assert!(tokens.contains("generate_random_with_len"));
+    }
+
+    #[test]
+    fn test_field_generator_distribution_attr_is_gated_behind_feature() {
+        let field: Field = parse_quote! { #[distribution(Normal(0.0, 1.0))] x: f64 };
+        let tokens = field_generator(&field).to_string();
+        assert!(tokens.contains("generate_random :: distribution :: sample"));
+        assert!(tokens.contains("rand_distr :: Normal :: new"));
+    }
+
+    #[test]
+    fn test_field_generator_default() {
+        let field: Field = parse_quote! { x: i32 };
+        let tokens = field_generator(&field).to_string();
+        assert!(tokens.contains("GenerateRandom :: generate_random_with_depth"));
+        assert!(tokens.contains("rng , depth"));
+        assert!(!tokens.contains("depth + 1"));
+        assert!(!tokens.contains("gen_range"));
+    }
+
+    #[test]
+    fn test_max_depth_expr_defaults_to_crate_constant() {
+        let attrs: Vec<Attribute> = Vec::new();
+        let tokens = max_depth_expr(&attrs).to_string();
+        assert!(tokens.contains("DEFAULT_MAX_DEPTH"));
+    }
+
+    #[test]
+    fn test_max_depth_expr_uses_override() {
+        let field: syn::DeriveInput = parse_quote! {
+            #[max_depth(4)]
+            struct Foo;
+        };
+        let tokens = max_depth_expr(&field.attrs).to_string();
+        assert_eq!(tokens, "4");
+    }
+}
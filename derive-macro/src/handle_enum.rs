@@ -1,42 +1,177 @@
 use super::generate_fields;
 use proc_macro2::{Ident, Literal, TokenStream};
 use quote::quote;
-use syn::{DataEnum, Variant};
+use syn::{DataEnum, GenericArgument, LitFloat, LitInt, PathArguments, Type, Variant};
 
-fn variant_weight(variant: &Variant) -> Literal {
+fn variant_weight(variant: &Variant) -> f64 {
     for attr in variant.attrs.iter() {
         if attr.path.is_ident("weight") {
-            return attr
-                .parse_args::<Literal>()
-                .expect("expected literal for `#[weight(...)]`");
+            if let Ok(lit) = attr.parse_args::<LitFloat>() {
+                return lit
+                    .base10_parse()
+                    .expect("invalid float literal for `#[weight(...)]`");
+            }
+            if let Ok(lit) = attr.parse_args::<LitInt>() {
+                return lit
+                    .base10_parse::<u64>()
+                    .expect("invalid integer literal for `#[weight(...)]`") as f64;
+            }
+            panic!("expected a numeric literal for `#[weight(...)]`");
+        }
+    }
+    1.0
+}
+
+/// Whether `ty` mentions `ident` anywhere, including inside generic
+/// arguments, arrays, tuples and references. Used to statically detect
+/// variant fields that recurse into the enum being derived (directly, or
+/// via `Box<Self>`, `Vec<Self>`, `Option<Self>`, ...).
+fn type_mentions_ident(ty: &Type, ident: &Ident) -> bool {
+    match ty {
+        Type::Path(path) => path.path.segments.iter().any(|segment| {
+            segment.ident == *ident
+                || segment.ident == "Self"
+                || match &segment.arguments {
+                    PathArguments::AngleBracketed(args) => args.args.iter().any(|arg| {
+                        matches!(arg, GenericArgument::Type(inner) if type_mentions_ident(inner, ident))
+                    }),
+                    _ => false,
+                }
+        }),
+        Type::Reference(reference) => type_mentions_ident(&reference.elem, ident),
+        Type::Array(array) => type_mentions_ident(&array.elem, ident),
+        Type::Tuple(tuple) => tuple.elems.iter().any(|elem| type_mentions_ident(elem, ident)),
+        _ => false,
+    }
+}
+
+fn variant_is_recursive(variant: &Variant, enum_ident: &Ident) -> bool {
+    variant
+        .fields
+        .iter()
+        .any(|field| type_mentions_ident(&field.ty, enum_ident))
+}
+
+/// Build the `[f64; N]` prefix-sum literal of `weights`, used to
+/// binary-search for a drawn value's variant in O(log N) via
+/// `partition_point` instead of a linear scan.
+fn cumulative_weights(weights: &[f64]) -> TokenStream {
+    let mut running = 0.0;
+    let sums = weights.iter().map(|weight| {
+        running += weight;
+        Literal::f64_suffixed(running)
+    });
+    quote! { [ #( #sums, )* ] }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_variant_weight_default_is_one() {
+        let variant: Variant = parse_quote! { A };
+        assert_eq!(variant_weight(&variant), 1.0);
+    }
+
+    #[test]
+    fn test_variant_weight_parses_integer_literal() {
+        let variant: Variant = parse_quote! { #[weight(3)] A };
+        assert_eq!(variant_weight(&variant), 3.0);
+    }
+
+    #[test]
+    fn test_variant_weight_parses_float_literal() {
+        let variant: Variant = parse_quote! { #[weight(2.5)] A };
+        assert_eq!(variant_weight(&variant), 2.5);
+    }
+
+    #[test]
+    fn test_cumulative_weights_is_prefix_sum() {
+        let tokens = cumulative_weights(&[1.0, 2.0, 3.0]).to_string();
+        assert!(tokens.contains("1f64"));
+        assert!(tokens.contains("3f64"));
+        assert!(tokens.contains("6f64"));
+    }
+
+    #[test]
+    fn test_cumulative_weights_partition_point_selects_expected_variant() {
+        // Mirrors the selection logic emitted into `generate_random_with_depth`:
+        // draw a value in [0, total) and binary-search the prefix sums for it.
+        let weights = [1.0, 2.0, 1.0];
+        let mut running = 0.0;
+        let cum: Vec<f64> = weights
+            .iter()
+            .map(|w| {
+                running += w;
+                running
+            })
+            .collect();
+
+        assert_eq!(cum.partition_point(|&c| c <= 0.5), 0);
+        assert_eq!(cum.partition_point(|&c| c <= 1.5), 1);
+        assert_eq!(cum.partition_point(|&c| c <= 2.9), 1);
+        assert_eq!(cum.partition_point(|&c| c <= 3.5), 2);
+    }
+
+    fn data_enum(input: syn::DeriveInput) -> DataEnum {
+        match input.data {
+            syn::Data::Enum(ty) => ty,
+            _ => panic!("expected an enum"),
         }
     }
-    Literal::u64_suffixed(1)
+
+    #[test]
+    fn test_generate_uses_given_max_depth_expr() {
+        let input: syn::DeriveInput = parse_quote! {
+            enum List { Nil, Cons(i32, Box<List>) }
+        };
+        let ident = input.ident.clone();
+        let max_depth: TokenStream = quote! { 4 };
+        let tokens = generate(&ident, data_enum(input), &max_depth).to_string();
+        assert!(tokens.contains("depth >= 4"));
+    }
+
+    #[test]
+    fn test_generate_recursive_field_does_not_increment_depth() {
+        let input: syn::DeriveInput = parse_quote! {
+            enum List { Nil, Cons(i32, Box<List>) }
+        };
+        let ident = input.ident.clone();
+        let max_depth: TokenStream = quote! { generate_random::DEFAULT_MAX_DEPTH };
+        let tokens = generate(&ident, data_enum(input), &max_depth).to_string();
+        assert!(tokens.contains("generate_random_with_depth (rng , depth)"));
+        assert!(!tokens.contains("depth + 1"));
+    }
 }
 
-pub fn generate(name: &Ident, ty: DataEnum) -> TokenStream {
-    let variant_weights = ty
-        .variants
-        .into_iter()
-        .enumerate()
-        .map(|(i, variant)| (i, variant_weight(&variant), variant));
+pub fn generate(name: &Ident, ty: DataEnum, max_depth: &TokenStream) -> TokenStream {
+    let variants: Vec<Variant> = ty.variants.into_iter().collect();
+
+    let weights: Vec<f64> = variants.iter().map(variant_weight).collect();
+    let recursive: Vec<bool> = variants
+        .iter()
+        .map(|variant| variant_is_recursive(variant, name))
+        .collect();
+
+    let num_variants = variants.len();
+    let cum = cumulative_weights(&weights);
+    let total_weight = Literal::f64_suffixed(weights.iter().sum());
+
+    let terminal_indices: Vec<usize> = (0..num_variants).filter(|&i| !recursive[i]).collect();
+    let has_terminal = !terminal_indices.is_empty() && terminal_indices.len() < num_variants;
+    let num_terminal = terminal_indices.len();
+
+    let terminal_weights: Vec<f64> = terminal_indices.iter().map(|&i| weights[i]).collect();
+    let terminal_cum = cumulative_weights(&terminal_weights);
+    let terminal_total_weight = Literal::f64_suffixed(terminal_weights.iter().sum());
+    let terminal_index_literals = terminal_indices.iter().map(|&i| Literal::usize_unsuffixed(i));
 
-    let mut arms = TokenStream::new();
     let mut arms_variant = TokenStream::new();
     let mut arms_variant_name = TokenStream::new();
-    let mut num_variants: usize = 0;
-
-    let mut total_weight = quote! { 0 };
-    for (index, weight, variant) in variant_weights {
-        let variant_name = variant.ident;
-        arms.extend(quote! {
-            let start = end;
-            let end = start + #weight;
-            if start <= value && value < end {
-                return generate_random::GenerateRandomVariant::generate_random_variant(rng, #index);
-            }
-        });
-
+    for (index, variant) in variants.into_iter().enumerate() {
+        let variant_name = variant.ident.clone();
         let fields = generate_fields(variant.fields);
         arms_variant.extend(quote! {
             #index => Self::#variant_name #fields,
@@ -46,19 +181,38 @@ pub fn generate(name: &Ident, ty: DataEnum) -> TokenStream {
         arms_variant_name.extend(quote! {
             #index => #variant_str,
         });
-
-        total_weight = quote! { #total_weight + #weight };
-        num_variants += 1;
     }
 
+    let terminal_selection = if has_terminal {
+        quote! {
+            if depth >= #max_depth {
+                const TERMINAL_CUM: [f64; #num_terminal] = #terminal_cum;
+                const TERMINAL_INDEX: [usize; #num_terminal] = [ #( #terminal_index_literals, )* ];
+
+                let value: f64 = rng.gen_range(0.0..#terminal_total_weight);
+                let pos = TERMINAL_CUM.partition_point(|&cum| cum <= value);
+                let variant = TERMINAL_INDEX[pos.min(TERMINAL_INDEX.len() - 1)];
+                return generate_random::GenerateRandomVariant::generate_random_variant_with_depth(rng, variant, depth);
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
     quote! {
         impl generate_random::GenerateRandom for #name {
             fn generate_random<R: rand::Rng + ?Sized>(rng: &mut R) -> Self {
-                let total_weight = #total_weight;
-                let value = rng.gen_range(0..total_weight);
-                let end = 0;
-                #arms
-                unreachable!()
+                generate_random::GenerateRandom::generate_random_with_depth(rng, 0)
+            }
+
+            fn generate_random_with_depth<R: rand::Rng + ?Sized>(rng: &mut R, depth: usize) -> Self {
+                #terminal_selection
+
+                const CUM: [f64; #num_variants] = #cum;
+
+                let value: f64 = rng.gen_range(0.0..#total_weight);
+                let variant = CUM.partition_point(|&cum| cum <= value);
+                generate_random::GenerateRandomVariant::generate_random_variant_with_depth(rng, variant, depth)
             }
         }
 
@@ -75,9 +229,17 @@ pub fn generate(name: &Ident, ty: DataEnum) -> TokenStream {
             }
 
             fn generate_random_variant<R: rand::Rng + ?Sized>(rng: &mut R, variant: usize) -> Self {
+                generate_random::GenerateRandomVariant::generate_random_variant_with_depth(rng, variant, 0)
+            }
+
+            fn generate_random_variant_with_depth<R: rand::Rng + ?Sized>(
+                rng: &mut R,
+                variant: usize,
+                depth: usize,
+            ) -> Self {
                 match variant {
                     #arms_variant
-                    _ => generate_random::GenerateRandom::generate_random(rng),
+                    _ => generate_random::GenerateRandom::generate_random_with_depth(rng, depth),
                 }
             }
         }
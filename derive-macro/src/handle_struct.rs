@@ -8,8 +8,44 @@ pub fn generate(name: &Ident, ty: DataStruct) -> TokenStream {
     quote! {
         impl generate_random::GenerateRandom for #name {
             fn generate_random<R: rand::Rng + ?Sized>(rng: &mut R) -> Self {
+                generate_random::GenerateRandom::generate_random_with_depth(rng, 0)
+            }
+
+            fn generate_random_with_depth<R: rand::Rng + ?Sized>(rng: &mut R, depth: usize) -> Self {
                 Self #fields
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::{parse_quote, Data, DeriveInput};
+
+    fn data_struct(input: DeriveInput) -> DataStruct {
+        match input.data {
+            Data::Struct(ty) => ty,
+            _ => panic!("expected a struct"),
+        }
+    }
+
+    #[test]
+    fn test_generate_delegates_to_generate_random_with_depth_at_zero() {
+        let input: DeriveInput = parse_quote! { struct Foo { x: i32 } };
+        let ident = input.ident.clone();
+        let tokens = generate(&ident, data_struct(input)).to_string();
+        assert!(tokens.contains("generate_random_with_depth (rng , 0)"));
+    }
+
+    #[test]
+    fn test_generate_field_does_not_increment_depth() {
+        let input: DeriveInput = parse_quote! {
+            struct ListNode { value: i32, next: Option<Box<ListNode>> }
+        };
+        let ident = input.ident.clone();
+        let tokens = generate(&ident, data_struct(input)).to_string();
+        assert!(tokens.contains("generate_random_with_depth (rng , depth)"));
+        assert!(!tokens.contains("depth + 1"));
+    }
+}
@@ -0,0 +1,62 @@
+mod attrs;
+mod handle_enum;
+mod handle_struct;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields};
+
+/// Derive an implementation of [`generate_random::GenerateRandom`].
+///
+/// See the documentation of the `GenerateRandom` derive macro as
+/// re-exported by `generate_random` for the supported variant, field
+/// and type-level attributes (including
+/// `#[max_depth(n)]`, which overrides
+/// [`generate_random::DEFAULT_MAX_DEPTH`] for the derived type).
+#[proc_macro_derive(
+    GenerateRandom,
+    attributes(weight, range, len, distribution, max_depth)
+)]
+pub fn derive_generate_random(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = input.ident;
+    let max_depth = attrs::max_depth_expr(&input.attrs);
+
+    let output = match input.data {
+        Data::Struct(ty) => handle_struct::generate(&name, ty),
+        Data::Enum(ty) => handle_enum::generate(&name, ty, &max_depth),
+        Data::Union(_) => panic!("`GenerateRandom` cannot be derived for unions"),
+    };
+
+    output.into()
+}
+
+/// Build the field-construction tokens for a struct or enum variant,
+/// honoring per-field `#[range(...)]` and `#[len(...)]` attributes.
+fn generate_fields(fields: Fields) -> TokenStream2 {
+    match fields {
+        Fields::Named(fields) => {
+            let fields = fields.named.into_iter().map(|field| {
+                let ident = field.ident.clone().unwrap();
+                let generator = attrs::field_generator(&field);
+                quote! {
+                    #ident: #generator
+                }
+            });
+            quote! {
+                { #( #fields, )* }
+            }
+        }
+        Fields::Unnamed(fields) => {
+            let fields = fields
+                .unnamed
+                .into_iter()
+                .map(|field| attrs::field_generator(&field));
+            quote! {
+                ( #( #fields, )* )
+            }
+        }
+        Fields::Unit => TokenStream2::new(),
+    }
+}
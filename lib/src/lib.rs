@@ -26,10 +26,28 @@
 /// of the [`trait@GenerateRandom`] trait.
 ///
 /// Enum variants can be given a `weight` attribute
-/// to change how often it is generated.
+/// to change how often it is generated, e.g. `#[weight(2)]`
+/// or `#[weight(2.5)]` for a fractional weight.
 /// By default, the weight is `1`.
 /// The probability of a variants is its weight
 /// divided by the sum over all variants.
+///
+/// Fields can be given a `range` attribute to bound
+/// the values generated for them, e.g. `#[range(0..100)]`
+/// or `#[range(-1.0..=1.0)]`. Fields whose type implements
+/// [`GenerateRandomLen`] can instead be given a `len`
+/// attribute, e.g. `#[len(2..16)]`, to bound the number of
+/// elements generated rather than the value itself.
+///
+/// With the `rand_distr` feature enabled, fields can also be given a
+/// `distribution` attribute to sample from a non-uniform distribution,
+/// e.g. `#[distribution(Normal(0.0, 1.0))]`, `#[distribution(Exp(2.0))]`,
+/// `#[distribution(Gamma(2.0, 5.0))]` or
+/// `#[distribution(Binomial(20, 0.3))]`.
+///
+/// The derived type itself can be given a `max_depth` attribute,
+/// e.g. `#[max_depth(4)]`, to override [`DEFAULT_MAX_DEPTH`] for that
+/// type's own recursion-depth guard.
 pub use generate_random_macro::GenerateRandom;
 
 /// Enable randomly generating values of a type.
@@ -39,8 +57,38 @@ pub use generate_random_macro::GenerateRandom;
 pub trait GenerateRandom {
     /// Create a new random value of this type.
     fn generate_random<R: rand::Rng + ?Sized>(rng: &mut R) -> Self;
+
+    /// Like [`generate_random`](Self::generate_random), but aware of how
+    /// deep into a recursive structure this value is being generated.
+    ///
+    /// Types that can recurse into themselves (most notably enums derived
+    /// with [`macro@GenerateRandom`] that hold `Box<Self>` fields) override
+    /// this to keep generation from overflowing the stack: such enums pick
+    /// only non-recursive variants once `depth` reaches [`DEFAULT_MAX_DEPTH`]
+    /// (or the type's own `#[max_depth(n)]` override), and the standard
+    /// collections draw their length from `0..8 - depth`, generating empty
+    /// collections from depth 8 on. Each such override is responsible for incrementing
+    /// `depth` by exactly one when it recurses into an inner value; callers
+    /// that merely forward a field's value (the `#[derive(GenerateRandom)]`
+    /// struct and enum impls among them) pass `depth` through unchanged.
+    /// The default implementation ignores `depth` and just calls
+    /// [`generate_random`](Self::generate_random).
+    fn generate_random_with_depth<R: rand::Rng + ?Sized>(rng: &mut R, _depth: usize) -> Self
+    where
+        Self: Sized,
+    {
+        Self::generate_random(rng)
+    }
 }
 
+/// Default recursion-depth limit honored by
+/// [`GenerateRandom::generate_random_with_depth`] implementations that
+/// switch to terminal (non-recursive) values once `depth` reaches it.
+///
+/// A type derived with [`macro@GenerateRandom`] can override this default
+/// for itself with a `#[max_depth(n)]` attribute.
+pub const DEFAULT_MAX_DEPTH: usize = 16;
+
 /// Enable randomly generating values of an enum
 /// with a predefined variant
 ///
@@ -55,6 +103,65 @@ pub trait GenerateRandomVariant {
 
     /// Create a randomly generated value with a predefied variant
     fn generate_random_variant<R: rand::Rng + ?Sized>(rng: &mut R, variant: usize) -> Self;
+
+    /// Like [`generate_random_variant`](Self::generate_random_variant), but
+    /// threading a recursion depth through to the variant's fields. See
+    /// [`GenerateRandom::generate_random_with_depth`].
+    fn generate_random_variant_with_depth<R: rand::Rng + ?Sized>(
+        rng: &mut R,
+        variant: usize,
+        _depth: usize,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        Self::generate_random_variant(rng, variant)
+    }
+}
+
+/// Generate a random value of `T` using [`rand::thread_rng`].
+///
+/// This is a convenience wrapper around
+/// [`GenerateRandom::generate_random`] for callers who don't need to
+/// manage their own [`rand::Rng`].
+///
+/// ```
+/// use generate_random::GenerateRandom;
+///
+/// #[derive(GenerateRandom)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// let point: Point = generate_random::random();
+/// ```
+pub fn random<T: GenerateRandom>() -> T {
+    T::generate_random(&mut rand::thread_rng())
+}
+
+/// Generate a random value of `T` from a deterministic `seed`.
+///
+/// The same `seed` always produces the same value for a given `T`, which
+/// is useful for reproducible test vectors.
+pub fn random_seeded<T: GenerateRandom>(seed: u64) -> T {
+    use rand::SeedableRng;
+
+    let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(seed);
+    T::generate_random(&mut rng)
+}
+
+/// Enable generating a random value of a type whose "length" can be
+/// bounded explicitly, such as a collection or a [`String`].
+///
+/// This backs the `#[len(...)]` field attribute of the
+/// [`macro@GenerateRandom`] derive macro.
+pub trait GenerateRandomLen: GenerateRandom {
+    /// Create a new random value whose length is drawn from `len_range`.
+    fn generate_random_with_len<R: rand::Rng + ?Sized>(
+        rng: &mut R,
+        len_range: std::ops::Range<usize>,
+    ) -> Self;
 }
 
 macro_rules! impl_generate_random {
@@ -96,12 +203,24 @@ impl<T: GenerateRandom> GenerateRandom for Option<T> {
             None
         }
     }
+
+    fn generate_random_with_depth<R: rand::Rng + ?Sized>(rng: &mut R, depth: usize) -> Self {
+        if bool::generate_random(rng) {
+            Some(T::generate_random_with_depth(rng, depth + 1))
+        } else {
+            None
+        }
+    }
 }
 
 impl<T: GenerateRandom, const N: usize> GenerateRandom for [T; N] {
     fn generate_random<R: rand::Rng + ?Sized>(rng: &mut R) -> Self {
         core::array::from_fn(|_| T::generate_random(rng))
     }
+
+    fn generate_random_with_depth<R: rand::Rng + ?Sized>(rng: &mut R, depth: usize) -> Self {
+        core::array::from_fn(|_| T::generate_random_with_depth(rng, depth + 1))
+    }
 }
 
 impl GenerateRandom for String {
@@ -113,11 +232,41 @@ impl GenerateRandom for String {
     }
 }
 
+impl GenerateRandomLen for String {
+    fn generate_random_with_len<R: rand::Rng + ?Sized>(
+        rng: &mut R,
+        len_range: std::ops::Range<usize>,
+    ) -> Self {
+        use rand::distributions::{Alphanumeric, DistString};
+
+        let len = rng.gen_range(len_range);
+        Alphanumeric.sample_string(rng, len)
+    }
+}
+
 impl<T: GenerateRandom> GenerateRandom for Vec<T> {
     fn generate_random<R: rand::Rng + ?Sized>(rng: &mut R) -> Self {
         let len = rng.gen_range(0..8);
         (0..len).map(|_| T::generate_random(rng)).collect()
     }
+
+    fn generate_random_with_depth<R: rand::Rng + ?Sized>(rng: &mut R, depth: usize) -> Self {
+        let max_len = 8usize.saturating_sub(depth);
+        let len = if max_len == 0 { 0 } else { rng.gen_range(0..max_len) };
+        (0..len)
+            .map(|_| T::generate_random_with_depth(rng, depth + 1))
+            .collect()
+    }
+}
+
+impl<T: GenerateRandom> GenerateRandomLen for Vec<T> {
+    fn generate_random_with_len<R: rand::Rng + ?Sized>(
+        rng: &mut R,
+        len_range: std::ops::Range<usize>,
+    ) -> Self {
+        let len = rng.gen_range(len_range);
+        (0..len).map(|_| T::generate_random(rng)).collect()
+    }
 }
 
 impl<T> GenerateRandom for std::collections::HashSet<T>
@@ -128,6 +277,27 @@ where
         let len = rng.gen_range(0..8);
         (0..len).map(|_| T::generate_random(rng)).collect()
     }
+
+    fn generate_random_with_depth<R: rand::Rng + ?Sized>(rng: &mut R, depth: usize) -> Self {
+        let max_len = 8usize.saturating_sub(depth);
+        let len = if max_len == 0 { 0 } else { rng.gen_range(0..max_len) };
+        (0..len)
+            .map(|_| T::generate_random_with_depth(rng, depth + 1))
+            .collect()
+    }
+}
+
+impl<T> GenerateRandomLen for std::collections::HashSet<T>
+where
+    T: GenerateRandom + std::cmp::Eq + std::hash::Hash,
+{
+    fn generate_random_with_len<R: rand::Rng + ?Sized>(
+        rng: &mut R,
+        len_range: std::ops::Range<usize>,
+    ) -> Self {
+        let len = rng.gen_range(len_range);
+        (0..len).map(|_| T::generate_random(rng)).collect()
+    }
 }
 
 impl<K, V> GenerateRandom for std::collections::HashMap<K, V>
@@ -141,12 +311,177 @@ where
             .map(|_| (K::generate_random(rng), V::generate_random(rng)))
             .collect()
     }
+
+    fn generate_random_with_depth<R: rand::Rng + ?Sized>(rng: &mut R, depth: usize) -> Self {
+        let max_len = 8usize.saturating_sub(depth);
+        let len = if max_len == 0 { 0 } else { rng.gen_range(0..max_len) };
+        (0..len)
+            .map(|_| {
+                (
+                    K::generate_random_with_depth(rng, depth + 1),
+                    V::generate_random_with_depth(rng, depth + 1),
+                )
+            })
+            .collect()
+    }
+}
+
+impl<K, V> GenerateRandomLen for std::collections::HashMap<K, V>
+where
+    K: GenerateRandom + std::cmp::Eq + std::hash::Hash,
+    V: GenerateRandom,
+{
+    fn generate_random_with_len<R: rand::Rng + ?Sized>(
+        rng: &mut R,
+        len_range: std::ops::Range<usize>,
+    ) -> Self {
+        let len = rng.gen_range(len_range);
+        (0..len)
+            .map(|_| (K::generate_random(rng), V::generate_random(rng)))
+            .collect()
+    }
+}
+
+impl<T: GenerateRandom + Ord> GenerateRandom for std::collections::BTreeSet<T> {
+    fn generate_random<R: rand::Rng + ?Sized>(rng: &mut R) -> Self {
+        let len = rng.gen_range(0..8);
+        (0..len).map(|_| T::generate_random(rng)).collect()
+    }
+
+    fn generate_random_with_depth<R: rand::Rng + ?Sized>(rng: &mut R, depth: usize) -> Self {
+        let max_len = 8usize.saturating_sub(depth);
+        let len = if max_len == 0 { 0 } else { rng.gen_range(0..max_len) };
+        (0..len)
+            .map(|_| T::generate_random_with_depth(rng, depth + 1))
+            .collect()
+    }
+}
+
+impl<K: GenerateRandom + Ord, V: GenerateRandom> GenerateRandom for std::collections::BTreeMap<K, V> {
+    fn generate_random<R: rand::Rng + ?Sized>(rng: &mut R) -> Self {
+        let len = rng.gen_range(0..8);
+        (0..len)
+            .map(|_| (K::generate_random(rng), V::generate_random(rng)))
+            .collect()
+    }
+
+    fn generate_random_with_depth<R: rand::Rng + ?Sized>(rng: &mut R, depth: usize) -> Self {
+        let max_len = 8usize.saturating_sub(depth);
+        let len = if max_len == 0 { 0 } else { rng.gen_range(0..max_len) };
+        (0..len)
+            .map(|_| {
+                (
+                    K::generate_random_with_depth(rng, depth + 1),
+                    V::generate_random_with_depth(rng, depth + 1),
+                )
+            })
+            .collect()
+    }
+}
+
+impl<T: GenerateRandom> GenerateRandom for std::collections::VecDeque<T> {
+    fn generate_random<R: rand::Rng + ?Sized>(rng: &mut R) -> Self {
+        let len = rng.gen_range(0..8);
+        (0..len).map(|_| T::generate_random(rng)).collect()
+    }
+
+    fn generate_random_with_depth<R: rand::Rng + ?Sized>(rng: &mut R, depth: usize) -> Self {
+        let max_len = 8usize.saturating_sub(depth);
+        let len = if max_len == 0 { 0 } else { rng.gen_range(0..max_len) };
+        (0..len)
+            .map(|_| T::generate_random_with_depth(rng, depth + 1))
+            .collect()
+    }
+}
+
+impl<T: GenerateRandom> GenerateRandom for std::collections::LinkedList<T> {
+    fn generate_random<R: rand::Rng + ?Sized>(rng: &mut R) -> Self {
+        let len = rng.gen_range(0..8);
+        (0..len).map(|_| T::generate_random(rng)).collect()
+    }
+
+    fn generate_random_with_depth<R: rand::Rng + ?Sized>(rng: &mut R, depth: usize) -> Self {
+        let max_len = 8usize.saturating_sub(depth);
+        let len = if max_len == 0 { 0 } else { rng.gen_range(0..max_len) };
+        (0..len)
+            .map(|_| T::generate_random_with_depth(rng, depth + 1))
+            .collect()
+    }
 }
 
 impl<T: GenerateRandom> GenerateRandom for Box<T> {
     fn generate_random<R: rand::Rng + ?Sized>(rng: &mut R) -> Self {
         Box::new(T::generate_random(rng))
     }
+
+    fn generate_random_with_depth<R: rand::Rng + ?Sized>(rng: &mut R, depth: usize) -> Self {
+        Box::new(T::generate_random_with_depth(rng, depth + 1))
+    }
+}
+
+impl<T: GenerateRandom> GenerateRandom for std::rc::Rc<T> {
+    fn generate_random<R: rand::Rng + ?Sized>(rng: &mut R) -> Self {
+        Self::new(T::generate_random(rng))
+    }
+
+    fn generate_random_with_depth<R: rand::Rng + ?Sized>(rng: &mut R, depth: usize) -> Self {
+        Self::new(T::generate_random_with_depth(rng, depth + 1))
+    }
+}
+
+impl<T: GenerateRandom> GenerateRandom for std::sync::Arc<T> {
+    fn generate_random<R: rand::Rng + ?Sized>(rng: &mut R) -> Self {
+        Self::new(T::generate_random(rng))
+    }
+
+    fn generate_random_with_depth<R: rand::Rng + ?Sized>(rng: &mut R, depth: usize) -> Self {
+        Self::new(T::generate_random_with_depth(rng, depth + 1))
+    }
+}
+
+macro_rules! impl_generate_random_nonzero {
+	( $( $nz:ty, $inner:ty, )+ ) => {
+		$(
+			impl GenerateRandom for $nz {
+				fn generate_random<R: rand::Rng + ?Sized>(rng: &mut R) -> Self {
+					loop {
+						if let Some(value) = <$nz>::new(<$inner>::generate_random(rng)) {
+							return value;
+						}
+					}
+				}
+			}
+		)+
+	}
+}
+
+impl_generate_random_nonzero! {
+    std::num::NonZeroU8, u8,
+    std::num::NonZeroI8, i8,
+    std::num::NonZeroU16, u16,
+    std::num::NonZeroI16, i16,
+    std::num::NonZeroU32, u32,
+    std::num::NonZeroI32, i32,
+    std::num::NonZeroU64, u64,
+    std::num::NonZeroI64, i64,
+    std::num::NonZeroU128, u128,
+    std::num::NonZeroI128, i128,
+    std::num::NonZeroUsize, usize,
+    std::num::NonZeroIsize, isize,
+}
+
+impl GenerateRandom for std::time::Duration {
+    fn generate_random<R: rand::Rng + ?Sized>(rng: &mut R) -> Self {
+        let secs = u64::generate_random(rng);
+        let nanos = rng.gen_range(0..1_000_000_000);
+        Self::new(secs, nanos)
+    }
+}
+
+impl<T: GenerateRandom> GenerateRandom for std::num::Wrapping<T> {
+    fn generate_random<R: rand::Rng + ?Sized>(rng: &mut R) -> Self {
+        Self(T::generate_random(rng))
+    }
 }
 
 impl<T: GenerateRandom> GenerateRandom for std::ops::Range<T> {
@@ -226,6 +561,28 @@ macro_rules! impl_generate_random_tuple {
 
 impl_generate_random_tuple!(A B C D E F G H I J K L);
 
+/// Support for the `#[distribution(...)]` field attribute of the
+/// [`macro@GenerateRandom`] derive macro.
+///
+/// Not part of the public API. The derive macro's generated code builds
+/// the distribution through `rand_distr` directly (so the crate using the
+/// attribute depends on `rand_distr` itself) and samples it through
+/// [`distribution::sample`](crate::distribution::sample), so a struct using
+/// `#[distribution(...)]` does not compile unless this feature is enabled.
+#[cfg(feature = "rand_distr")]
+#[doc(hidden)]
+pub mod distribution {
+    use rand_distr::Distribution;
+
+    pub fn sample<D, T, R>(distribution: D, rng: &mut R) -> T
+    where
+        D: Distribution<T>,
+        R: rand::Rng + ?Sized,
+    {
+        distribution.sample(rng)
+    }
+}
+
 #[cfg(feature = "cgmath")]
 mod impl_cgmath {
     use super::*;
@@ -313,4 +670,80 @@ mod tests {
         let mut rng = rng();
         assert_eq!(u8::generate_random(&mut rng), 55);
     }
+
+    #[test]
+    fn test_random_seeded_is_deterministic() {
+        let a: u64 = random_seeded(37);
+        let b: u64 = random_seeded(37);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_vec_generate_random_with_len_respects_bound() {
+        let mut rng = rng();
+        for _ in 0..32 {
+            let value = Vec::<u8>::generate_random_with_len(&mut rng, 2..5);
+            assert!((2..5).contains(&value.len()));
+        }
+    }
+
+    #[test]
+    fn test_string_generate_random_with_len_respects_bound() {
+        let mut rng = rng();
+        for _ in 0..32 {
+            let value = String::generate_random_with_len(&mut rng, 2..5);
+            assert!((2..5).contains(&value.chars().count()));
+        }
+    }
+
+    #[test]
+    fn test_vec_generate_random_with_depth_shrinks_to_empty_past_max_len() {
+        let mut rng = rng();
+        let value = Vec::<u8>::generate_random_with_depth(&mut rng, 8);
+        assert!(value.is_empty());
+    }
+
+    #[test]
+    fn test_hash_map_generate_random_with_depth_shrinks_to_empty_past_max_len() {
+        let mut rng = rng();
+        let value = std::collections::HashMap::<u8, u8>::generate_random_with_depth(&mut rng, 8);
+        assert!(value.is_empty());
+    }
+
+    #[test]
+    fn test_btree_map_generate_random_with_depth_shrinks_to_empty_past_max_len() {
+        let mut rng = rng();
+        let value = std::collections::BTreeMap::<u8, u8>::generate_random_with_depth(&mut rng, 8);
+        assert!(value.is_empty());
+    }
+
+    #[test]
+    fn test_non_zero_u8_is_never_zero() {
+        let mut rng = rng();
+        for _ in 0..32 {
+            assert_ne!(std::num::NonZeroU8::generate_random(&mut rng).get(), 0);
+        }
+    }
+
+    #[test]
+    fn test_duration_nanos_in_range() {
+        let mut rng = rng();
+        let value = std::time::Duration::generate_random(&mut rng);
+        assert!(value.subsec_nanos() < 1_000_000_000);
+    }
+
+    #[test]
+    fn test_wrapping_delegates_to_inner() {
+        let mut rng = rng();
+        let value = std::num::Wrapping::<u8>::generate_random(&mut rng);
+        assert_eq!(value.0, 55);
+    }
+
+    #[test]
+    fn test_rc_and_arc_generate_random() {
+        let mut rng = rng();
+        assert_eq!(*std::rc::Rc::<u8>::generate_random(&mut rng), 55);
+        let mut rng = self::rng();
+        assert_eq!(*std::sync::Arc::<u8>::generate_random(&mut rng), 55);
+    }
 }